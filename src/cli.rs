@@ -0,0 +1,147 @@
+//! Command-line entry point.
+
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand, ValueEnum};
+
+use crate::config::Config;
+use crate::diagnostic::{self, Diagnostic};
+use crate::driver::DriverInvocation;
+use crate::error::{LucidSharkError, Result};
+use crate::fix::{self, AutofixOptions};
+use crate::lints;
+use crate::sarif;
+
+#[derive(Parser)]
+#[command(name = "lucidshark", about = "Applies and reports clippy diagnostics")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Collect diagnostics and print them, either for humans or as SARIF.
+    Check {
+        /// Directory containing the crate's Cargo.toml.
+        #[arg(long, default_value = ".")]
+        manifest_dir: PathBuf,
+        /// Output format.
+        #[arg(long, value_enum, default_value_t = OutputFormat::Human)]
+        format: OutputFormat,
+    },
+    /// Apply every machine-applicable clippy suggestion in place.
+    Fix {
+        /// Directory containing the crate's Cargo.toml.
+        #[arg(long, default_value = ".")]
+        manifest_dir: PathBuf,
+        /// Maximum number of collect/apply passes.
+        #[arg(long, default_value_t = 4)]
+        max_passes: usize,
+        /// Compute fixes without writing any files.
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Run `clippy-driver` directly, bypassing `cargo`, passing the given
+    /// flags through an `@argfile`.
+    Drive {
+        /// Path to the `clippy-driver` binary to invoke.
+        #[arg(long)]
+        driver_path: PathBuf,
+        /// Flags to forward to the driver, e.g. `-- --edition=2021 main.rs`.
+        #[arg(trailing_var_arg = true)]
+        flags: Vec<String>,
+    },
+    /// Dump the full lint catalog LucidShark recognizes.
+    CollectMetadata {
+        /// Output format.
+        #[arg(long, value_enum, default_value_t = MetadataFormat::Json)]
+        format: MetadataFormat,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum MetadataFormat {
+    Json,
+    Markdown,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum OutputFormat {
+    Human,
+    Sarif,
+}
+
+pub fn run() -> Result<()> {
+    let cli = Cli::parse();
+    match cli.command {
+        Command::Check {
+            manifest_dir,
+            format,
+        } => {
+            let config = Config::load(&manifest_dir)?;
+            let diagnostics = diagnostic::collect_with_args(&manifest_dir, &config.to_driver_flags())?;
+            let diagnostics = config.apply(diagnostics);
+            match format {
+                OutputFormat::Human => print_human(&diagnostics),
+                OutputFormat::Sarif => {
+                    let log = sarif::build(&diagnostics);
+                    println!("{}", serde_json::to_string_pretty(&log)?);
+                }
+            }
+            Ok(())
+        }
+        Command::Fix {
+            manifest_dir,
+            max_passes,
+            dry_run,
+        } => {
+            let options = AutofixOptions {
+                max_passes,
+                dry_run,
+            };
+            let report = fix::run(&manifest_dir, &options)?;
+            fix::print_summary(&report);
+            Ok(())
+        }
+        Command::Drive { driver_path, flags } => {
+            let mut invocation = DriverInvocation::new(driver_path);
+            for flag in flags {
+                invocation = invocation.flag(flag);
+            }
+            let output = invocation.run()?;
+            print!("{}", String::from_utf8_lossy(&output.stdout));
+            eprint!("{}", String::from_utf8_lossy(&output.stderr));
+            if !output.status.success() {
+                return Err(LucidSharkError::DriverFailed {
+                    status: output.status.code(),
+                });
+            }
+            Ok(())
+        }
+        Command::CollectMetadata { format } => {
+            match format {
+                MetadataFormat::Json => println!("{}", lints::to_json()?),
+                MetadataFormat::Markdown => print!("{}", lints::to_markdown()),
+            }
+            Ok(())
+        }
+    }
+}
+
+fn print_human(diagnostics: &[Diagnostic]) {
+    if diagnostics.is_empty() {
+        println!("no diagnostics found");
+        return;
+    }
+    for diagnostic in diagnostics {
+        println!(
+            "{}:{}:{}: {} [{}]",
+            diagnostic.span.file.display(),
+            diagnostic.span.line_start,
+            diagnostic.span.column_start,
+            diagnostic.message,
+            diagnostic.lint,
+        );
+    }
+}
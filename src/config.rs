@@ -0,0 +1,258 @@
+//! `lucidshark.toml` configuration: allow/deny lists and per-lint severity
+//! overrides, loaded from the analyzed crate's root.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::diagnostic::{Diagnostic, Level};
+use crate::error::{LucidSharkError, Result};
+
+const CONFIG_FILE_NAME: &str = "lucidshark.toml";
+
+#[derive(Debug, Default, Deserialize)]
+struct RawConfig {
+    #[serde(default)]
+    allow: Vec<String>,
+    #[serde(default)]
+    deny: Vec<String>,
+    #[serde(default)]
+    severity: BTreeMap<String, String>,
+}
+
+/// Parsed and validated `lucidshark.toml`.
+#[derive(Debug, Default)]
+pub struct Config {
+    pub allow: Vec<String>,
+    pub deny: Vec<String>,
+    pub severity: BTreeMap<String, Level>,
+}
+
+impl Config {
+    /// Loads `lucidshark.toml` from `crate_root`. Missing files are not an
+    /// error: they just mean "use the defaults".
+    pub fn load(crate_root: &Path) -> Result<Config> {
+        let path = crate_root.join(CONFIG_FILE_NAME);
+        if !path.exists() {
+            return Ok(Config::default());
+        }
+
+        let contents = fs::read_to_string(&path)?;
+        let raw: RawConfig = toml::from_str(&contents)
+            .map_err(|err| LucidSharkError::Other(format!("{CONFIG_FILE_NAME}: {err}")))?;
+        Config::from_raw(raw)
+    }
+
+    fn from_raw(raw: RawConfig) -> Result<Config> {
+        for lint in raw.allow.iter().chain(raw.deny.iter()).chain(raw.severity.keys()) {
+            if crate::lints::find(lint).is_none() {
+                return Err(LucidSharkError::Other(format!(
+                    "{CONFIG_FILE_NAME}: unknown lint `{lint}`"
+                )));
+            }
+        }
+
+        let mut severity = BTreeMap::new();
+        for (lint, level) in raw.severity {
+            severity.insert(lint, parse_level(&level)?);
+        }
+
+        Ok(Config {
+            allow: raw.allow,
+            deny: raw.deny,
+            severity,
+        })
+    }
+
+    /// Translates this configuration into `-A`/`-W`/`-D` flags to forward to
+    /// clippy. A severity override wins over a plain allow/deny for the same
+    /// lint, matching how rustc itself treats the last flag for a lint as
+    /// authoritative.
+    pub fn to_driver_flags(&self) -> Vec<String> {
+        let mut flags = Vec::new();
+        for lint in &self.allow {
+            flags.push(format!("-A{lint}"));
+        }
+        for lint in &self.deny {
+            flags.push(format!("-D{lint}"));
+        }
+        for (lint, level) in &self.severity {
+            let prefix = match level {
+                Level::Error => "-D",
+                _ => "-W",
+            };
+            flags.push(format!("{prefix}{lint}"));
+        }
+        flags
+    }
+
+    /// Post-filters already-parsed diagnostics so configuration is honored
+    /// even for lints that can only be acted on after the fact: an allowed
+    /// lint is dropped entirely unless it also has a severity override, and
+    /// a severity override replaces the diagnostic's level.
+    pub fn apply(&self, diagnostics: Vec<Diagnostic>) -> Vec<Diagnostic> {
+        diagnostics
+            .into_iter()
+            .filter(|d| !self.allow.contains(&d.lint) || self.severity.contains_key(&d.lint))
+            .map(|mut d| {
+                if let Some(level) = self.severity.get(&d.lint) {
+                    d.level = *level;
+                }
+                d
+            })
+            .collect()
+    }
+}
+
+fn parse_level(raw: &str) -> Result<Level> {
+    match raw {
+        "error" => Ok(Level::Error),
+        "warning" | "warn" => Ok(Level::Warning),
+        "note" => Ok(Level::Note),
+        "help" => Ok(Level::Help),
+        other => Err(LucidSharkError::Other(format!(
+            "{CONFIG_FILE_NAME}: unknown severity `{other}`"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostic::Span;
+
+    fn diagnostic(lint: &str, level: Level) -> Diagnostic {
+        Diagnostic {
+            lint: lint.to_string(),
+            level,
+            message: "example".to_string(),
+            span: Span {
+                file: Path::new("src/lib.rs").to_path_buf(),
+                byte_start: 0,
+                byte_end: 1,
+                line_start: 1,
+                line_end: 1,
+                column_start: 1,
+                column_end: 2,
+            },
+            suggested_edits: Vec::new(),
+            applicability: None,
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_lint_in_allow() {
+        let raw = RawConfig {
+            allow: vec!["clippy::not_a_real_lint".to_string()],
+            ..RawConfig::default()
+        };
+        let err = Config::from_raw(raw).unwrap_err().to_string();
+        assert!(err.contains("clippy::not_a_real_lint"), "{err}");
+    }
+
+    #[test]
+    fn rejects_unknown_lint_in_deny() {
+        let raw = RawConfig {
+            deny: vec!["clippy::not_a_real_lint".to_string()],
+            ..RawConfig::default()
+        };
+        assert!(Config::from_raw(raw).is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_lint_in_severity() {
+        let mut severity = BTreeMap::new();
+        severity.insert("clippy::not_a_real_lint".to_string(), "error".to_string());
+        let raw = RawConfig {
+            severity,
+            ..RawConfig::default()
+        };
+        assert!(Config::from_raw(raw).is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_severity_level() {
+        let mut severity = BTreeMap::new();
+        severity.insert("clippy::redundant_clone".to_string(), "catastrophic".to_string());
+        let raw = RawConfig {
+            severity,
+            ..RawConfig::default()
+        };
+        assert!(Config::from_raw(raw).is_err());
+    }
+
+    #[test]
+    fn accepts_known_lints_and_parses_severity() {
+        let mut severity = BTreeMap::new();
+        severity.insert("clippy::redundant_clone".to_string(), "error".to_string());
+        let raw = RawConfig {
+            allow: vec!["clippy::needless_return".to_string()],
+            deny: vec!["unused_imports".to_string()],
+            severity,
+        };
+        let config = Config::from_raw(raw).unwrap();
+        assert_eq!(config.allow, vec!["clippy::needless_return".to_string()]);
+        assert_eq!(config.deny, vec!["unused_imports".to_string()]);
+        assert_eq!(
+            config.severity.get("clippy::redundant_clone"),
+            Some(&Level::Error)
+        );
+    }
+
+    #[test]
+    fn to_driver_flags_translates_allow_deny_and_severity() {
+        let mut severity = BTreeMap::new();
+        severity.insert("clippy::manual_map".to_string(), Level::Error);
+        let config = Config {
+            allow: vec!["clippy::needless_return".to_string()],
+            deny: vec!["unused_imports".to_string()],
+            severity,
+        };
+        let flags = config.to_driver_flags();
+        assert!(flags.contains(&"-Aclippy::needless_return".to_string()));
+        assert!(flags.contains(&"-Dunused_imports".to_string()));
+        assert!(flags.contains(&"-Dclippy::manual_map".to_string()));
+    }
+
+    #[test]
+    fn to_driver_flags_uses_warn_prefix_for_non_error_severity() {
+        let mut severity = BTreeMap::new();
+        severity.insert("clippy::needless_return".to_string(), Level::Warning);
+        let config = Config {
+            severity,
+            ..Config::default()
+        };
+        assert!(config
+            .to_driver_flags()
+            .contains(&"-Wclippy::needless_return".to_string()));
+    }
+
+    #[test]
+    fn apply_drops_allowed_lints() {
+        let config = Config {
+            allow: vec!["clippy::needless_return".to_string()],
+            ..Config::default()
+        };
+        let diagnostics = vec![
+            diagnostic("clippy::needless_return", Level::Warning),
+            diagnostic("clippy::manual_map", Level::Warning),
+        ];
+        let filtered = config.apply(diagnostics);
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].lint, "clippy::manual_map");
+    }
+
+    #[test]
+    fn apply_overrides_severity() {
+        let mut severity = BTreeMap::new();
+        severity.insert("clippy::redundant_clone".to_string(), Level::Error);
+        let config = Config {
+            severity,
+            ..Config::default()
+        };
+        let filtered = config.apply(vec![diagnostic("clippy::redundant_clone", Level::Warning)]);
+        assert_eq!(filtered[0].level, Level::Error);
+    }
+}
@@ -0,0 +1,257 @@
+//! Parsing of compiler/clippy diagnostics from `--message-format=json` output
+//! into the flattened shape the rest of LucidShark works with.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use serde::Deserialize;
+
+use crate::error::Result;
+
+/// How confident the compiler is that applying a suggestion is safe.
+///
+/// Only [`Applicability::MachineApplicable`] suggestions are safe to apply
+/// without a human looking at the diff first.
+///
+/// Variant names match rustc's `--message-format=json` output verbatim
+/// (`"MachineApplicable"`, not `"machine-applicable"`), so no `rename_all`
+/// is needed here.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum Applicability {
+    MachineApplicable,
+    MaybeIncorrect,
+    HasPlaceholders,
+    Unspecified,
+}
+
+/// Severity of a diagnostic, mirroring rustc's own levels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Error,
+    Warning,
+    Note,
+    Help,
+}
+
+impl Level {
+    fn parse(raw: &str) -> Level {
+        match raw {
+            "error" => Level::Error,
+            "note" => Level::Note,
+            "help" => Level::Help,
+            _ => Level::Warning,
+        }
+    }
+}
+
+/// Byte and line/column range of a diagnostic within a single file.
+#[derive(Debug, Clone)]
+pub struct Span {
+    pub file: PathBuf,
+    pub byte_start: usize,
+    pub byte_end: usize,
+    pub line_start: usize,
+    pub line_end: usize,
+    pub column_start: usize,
+    pub column_end: usize,
+}
+
+/// One byte-range replacement that is part of a suggestion. A single
+/// suggestion can carry more than one of these (e.g. `needless_return`
+/// replaces the `return <expr>` with `<expr>` in one edit and drops the now
+/// -orphaned trailing `;` in another), and all of them must land together
+/// for the suggestion to still be valid code.
+#[derive(Debug, Clone)]
+pub struct Edit {
+    pub byte_start: usize,
+    pub byte_end: usize,
+    pub replacement: String,
+}
+
+/// A single flattened diagnostic, ready for fixing, reporting, or filtering.
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    /// Fully qualified lint name, e.g. `clippy::redundant_clone`.
+    pub lint: String,
+    pub level: Level,
+    pub message: String,
+    /// Where the lint itself was raised - NOT necessarily where the fix
+    /// should be spliced in; see [`Diagnostic::suggested_edits`].
+    pub span: Span,
+    /// The suggestion's own byte ranges, which can differ from `span`
+    /// entirely (rustc often raises the lint on a narrower or differently
+    /// -placed span than the one it suggests replacing). Empty if the
+    /// compiler offered no suggestion.
+    pub suggested_edits: Vec<Edit>,
+    pub applicability: Option<Applicability>,
+}
+
+/// Runs `cargo clippy --message-format=json` in `manifest_dir` and flattens
+/// the resulting compiler messages into [`Diagnostic`]s.
+///
+/// Only diagnostics that carry a lint code (i.e. not plain rustc notes) and
+/// have a primary span are returned.
+pub fn collect(manifest_dir: &Path) -> Result<Vec<Diagnostic>> {
+    collect_with_args(manifest_dir, &[])
+}
+
+/// Like [`collect`], but forwards `extra_args` to clippy after `--`, e.g.
+/// the `-A`/`-W`/`-D` flags translated from a [`crate::config::Config`].
+pub fn collect_with_args(manifest_dir: &Path, extra_args: &[String]) -> Result<Vec<Diagnostic>> {
+    let mut command = Command::new("cargo");
+    command
+        .arg("clippy")
+        .arg("--message-format=json")
+        .current_dir(manifest_dir);
+    if !extra_args.is_empty() {
+        command.arg("--").args(extra_args);
+    }
+    let output = command.stdout(Stdio::piped()).stderr(Stdio::null()).output()?;
+
+    let mut diagnostics = Vec::new();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let Ok(message) = serde_json::from_str::<raw::CargoMessage>(line) else {
+            continue;
+        };
+        if message.reason != "compiler-message" {
+            continue;
+        }
+        if let Some(inner) = message.message {
+            diagnostics.extend(flatten(inner));
+        }
+    }
+    Ok(diagnostics)
+}
+
+fn flatten(message: raw::RustcMessage) -> Vec<Diagnostic> {
+    let Some(code) = message.code.as_ref().map(|c| c.code.clone()) else {
+        return Vec::new();
+    };
+    let level = Level::parse(&message.level);
+    // The actual fix usually isn't on the diagnostic's own primary span: for
+    // lints like `redundant_clone`, `manual_map`, and `needless_return`,
+    // rustc attaches `suggested_replacement`/`suggestion_applicability` to a
+    // span on a `help` child diagnostic instead - and that span's byte range
+    // is unrelated to the primary span's, so it has to be carried separately
+    // rather than folded into `span`.
+    let suggestion = find_suggestion(&message);
+
+    message
+        .spans
+        .iter()
+        .filter(|span| span.is_primary)
+        .map(|span| Diagnostic {
+            lint: code.clone(),
+            level,
+            message: message.message.clone(),
+            suggested_edits: suggestion.as_ref().map(|s| s.edits.clone()).unwrap_or_default(),
+            applicability: suggestion.as_ref().map(|s| s.applicability),
+            span: Span {
+                file: PathBuf::from(&span.file_name),
+                byte_start: span.byte_start,
+                byte_end: span.byte_end,
+                line_start: span.line_start,
+                line_end: span.line_end,
+                column_start: span.column_start,
+                column_end: span.column_end,
+            },
+        })
+        .collect()
+}
+
+struct Suggestion {
+    edits: Vec<Edit>,
+    applicability: Applicability,
+}
+
+/// Looks for a suggestion on `message`'s own spans first, then recurses into
+/// its `children` (rustc nests the fix under a `help` sub-diagnostic rather
+/// than the lint's own primary span). A suggestion can be made up of more
+/// than one span - e.g. `needless_return` edits both the `return <expr>`
+/// and, separately, the trailing `;` - so every span on the matching
+/// message is gathered as one [`Suggestion`], not just the first.
+fn find_suggestion(message: &raw::RustcMessage) -> Option<Suggestion> {
+    let edits: Vec<(Edit, Applicability)> = message.spans.iter().filter_map(span_edit).collect();
+
+    if let Some((_, applicability)) = edits.first() {
+        return Some(Suggestion {
+            applicability: *applicability,
+            edits: edits.into_iter().map(|(edit, _)| edit).collect(),
+        });
+    }
+
+    message.children.iter().find_map(find_suggestion)
+}
+
+fn span_edit(span: &raw::RustcSpan) -> Option<(Edit, Applicability)> {
+    let replacement = span.suggested_replacement.clone()?;
+    let applicability = span.suggestion_applicability?;
+    Some((
+        Edit {
+            byte_start: span.byte_start,
+            byte_end: span.byte_end,
+            replacement,
+        },
+        applicability,
+    ))
+}
+
+/// Groups diagnostics by the file their primary span points at. Accepts
+/// either owned or borrowed diagnostics so callers that have already
+/// filtered down to a `Vec<&Diagnostic>` don't need to collect again.
+pub fn by_file<'a, I>(diagnostics: I) -> HashMap<PathBuf, Vec<&'a Diagnostic>>
+where
+    I: IntoIterator<Item = &'a Diagnostic>,
+{
+    let mut grouped: HashMap<PathBuf, Vec<&'a Diagnostic>> = HashMap::new();
+    for diagnostic in diagnostics {
+        grouped
+            .entry(diagnostic.span.file.clone())
+            .or_default()
+            .push(diagnostic);
+    }
+    grouped
+}
+
+/// Raw shapes mirroring `cargo ... --message-format=json` output, used only
+/// as a deserialization target before we flatten them into [`Diagnostic`].
+mod raw {
+    use super::Applicability;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize)]
+    pub struct CargoMessage {
+        pub reason: String,
+        pub message: Option<RustcMessage>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    pub struct RustcMessage {
+        pub message: String,
+        pub level: String,
+        pub code: Option<RustcCode>,
+        pub spans: Vec<RustcSpan>,
+        #[serde(default)]
+        pub children: Vec<RustcMessage>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    pub struct RustcCode {
+        pub code: String,
+    }
+
+    #[derive(Debug, Deserialize)]
+    pub struct RustcSpan {
+        pub file_name: String,
+        pub byte_start: usize,
+        pub byte_end: usize,
+        pub line_start: usize,
+        pub line_end: usize,
+        pub column_start: usize,
+        pub column_end: usize,
+        pub is_primary: bool,
+        pub suggested_replacement: Option<String>,
+        pub suggestion_applicability: Option<Applicability>,
+    }
+}
@@ -0,0 +1,234 @@
+//! Drives the underlying `clippy-driver` binary directly via an `@argfile`,
+//! for callers that don't want to go through `cargo clippy`.
+
+use std::env;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+use tempfile::NamedTempFile;
+
+use crate::error::{LucidSharkError, Result};
+
+/// A single invocation of `clippy-driver`, built up before being run.
+pub struct DriverInvocation {
+    pub driver_path: PathBuf,
+    pub flags: Vec<String>,
+}
+
+impl DriverInvocation {
+    pub fn new(driver_path: impl Into<PathBuf>) -> Self {
+        DriverInvocation {
+            driver_path: driver_path.into(),
+            flags: Vec::new(),
+        }
+    }
+
+    pub fn flag(mut self, flag: impl Into<String>) -> Self {
+        self.flags.push(flag.into());
+        self
+    }
+
+    /// Resolves the sysroot to pass to the driver, following rustc's own
+    /// precedence so that a caller-supplied `--sysroot` always wins:
+    ///
+    /// 1. A `--sysroot` already present among `self.flags`.
+    /// 2. A `--sysroot` already embedded in `RUSTFLAGS` - in this case we
+    ///    return it but the caller must not append a second `--sysroot`,
+    ///    since the driver already sees it via `RUSTFLAGS` and errors out
+    ///    if it's passed twice.
+    /// 3. The `SYSROOT` environment variable.
+    /// 4. `rustc --print sysroot`.
+    pub fn resolve_sysroot(&self) -> Result<Option<String>> {
+        if let Some(sysroot) = find_sysroot_flag(&self.flags) {
+            return Ok(Some(sysroot));
+        }
+
+        let rustflags = env::var("RUSTFLAGS").unwrap_or_default();
+        if let Some(sysroot) = find_sysroot_flag(&split_rustflags(&rustflags)) {
+            return Ok(Some(sysroot));
+        }
+
+        if let Ok(sysroot) = env::var("SYSROOT") {
+            return Ok(Some(sysroot));
+        }
+
+        query_rustc_sysroot()
+    }
+
+    /// Writes `self.flags` to a temp file, one argument per line, adding a
+    /// resolved `--sysroot` only if neither `self.flags` nor `RUSTFLAGS`
+    /// already carries one. Returns the temp file so its path can be used
+    /// as `@<path>` on the driver's command line; the file is cleaned up
+    /// when dropped.
+    pub fn write_arg_file(&self) -> Result<NamedTempFile> {
+        let rustflags_has_sysroot =
+            find_sysroot_flag(&split_rustflags(&env::var("RUSTFLAGS").unwrap_or_default())).is_some();
+        let flags_have_sysroot = find_sysroot_flag(&self.flags).is_some();
+
+        let mut file = NamedTempFile::new()?;
+        for flag in &self.flags {
+            writeln!(file, "{flag}")?;
+        }
+        if !flags_have_sysroot && !rustflags_has_sysroot {
+            if let Some(sysroot) = self.resolve_sysroot()? {
+                writeln!(file, "--sysroot={sysroot}")?;
+            }
+        }
+        Ok(file)
+    }
+
+    /// Runs `clippy-driver @<arg_file>`.
+    pub fn run(&self) -> Result<Output> {
+        let arg_file = self.write_arg_file()?;
+        let arg_file_arg = format!("@{}", arg_file.path().display());
+        let output = Command::new(&self.driver_path).arg(arg_file_arg).output()?;
+        Ok(output)
+    }
+}
+
+fn find_sysroot_flag(flags: &[String]) -> Option<String> {
+    for (index, flag) in flags.iter().enumerate() {
+        if let Some(value) = flag.strip_prefix("--sysroot=") {
+            return Some(value.to_string());
+        }
+        if flag == "--sysroot" {
+            return flags.get(index + 1).cloned();
+        }
+    }
+    None
+}
+
+fn split_rustflags(rustflags: &str) -> Vec<String> {
+    rustflags.split_whitespace().map(str::to_string).collect()
+}
+
+fn query_rustc_sysroot() -> Result<Option<String>> {
+    let output = Command::new("rustc").arg("--print").arg("sysroot").output()?;
+    if !output.status.success() {
+        return Err(LucidSharkError::DriverFailed {
+            status: output.status.code(),
+        });
+    }
+    Ok(Some(String::from_utf8_lossy(&output.stdout).trim().to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    // `SYSROOT`/`RUSTFLAGS` are process-wide, so tests that touch them must
+    // not run concurrently with each other.
+    static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+    /// Runs `body` with the given environment variables set (or removed, for
+    /// `None`), restoring the previous values afterwards.
+    fn with_env(vars: &[(&str, Option<&str>)], body: impl FnOnce()) {
+        let _guard = ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let previous: Vec<(&str, Option<String>)> =
+            vars.iter().map(|(name, _)| (*name, env::var(name).ok())).collect();
+
+        for (name, value) in vars {
+            // SAFETY: serialized by ENV_LOCK, and restored below.
+            unsafe {
+                match value {
+                    Some(value) => env::set_var(name, value),
+                    None => env::remove_var(name),
+                }
+            }
+        }
+
+        body();
+
+        for (name, value) in previous {
+            // SAFETY: serialized by ENV_LOCK.
+            unsafe {
+                match value {
+                    Some(value) => env::set_var(name, value),
+                    None => env::remove_var(name),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn command_line_flag_wins_over_rustflags_and_env() {
+        with_env(
+            &[
+                ("SYSROOT", Some("/env/sysroot")),
+                ("RUSTFLAGS", Some("--sysroot=/rustflags/sysroot")),
+            ],
+            || {
+                let invocation = DriverInvocation::new("clippy-driver").flag("--sysroot=/flag/sysroot");
+                assert_eq!(
+                    invocation.resolve_sysroot().unwrap(),
+                    Some("/flag/sysroot".to_string())
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn rustflags_sysroot_wins_over_env_sysroot() {
+        with_env(
+            &[
+                ("SYSROOT", Some("/env/sysroot")),
+                ("RUSTFLAGS", Some("--sysroot=/rustflags/sysroot")),
+            ],
+            || {
+                let invocation = DriverInvocation::new("clippy-driver");
+                assert_eq!(
+                    invocation.resolve_sysroot().unwrap(),
+                    Some("/rustflags/sysroot".to_string())
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn env_sysroot_used_when_nothing_else_present() {
+        with_env(&[("SYSROOT", Some("/env/sysroot")), ("RUSTFLAGS", None)], || {
+            let invocation = DriverInvocation::new("clippy-driver");
+            assert_eq!(
+                invocation.resolve_sysroot().unwrap(),
+                Some("/env/sysroot".to_string())
+            );
+        });
+    }
+
+    #[test]
+    fn falls_back_to_rustc_print_sysroot() {
+        with_env(&[("SYSROOT", None), ("RUSTFLAGS", None)], || {
+            let invocation = DriverInvocation::new("clippy-driver");
+            let sysroot = invocation.resolve_sysroot().unwrap();
+            assert!(sysroot.is_some_and(|s| !s.is_empty()));
+        });
+    }
+
+    #[test]
+    fn write_arg_file_does_not_duplicate_sysroot_already_in_rustflags() {
+        with_env(
+            &[("SYSROOT", None), ("RUSTFLAGS", Some("--sysroot=/rustflags/sysroot"))],
+            || {
+                let invocation = DriverInvocation::new("clippy-driver").flag("-Cdebug-assertions");
+                let file = invocation.write_arg_file().unwrap();
+                let contents = std::fs::read_to_string(file.path()).unwrap();
+                assert!(
+                    !contents.contains("--sysroot"),
+                    "must not append a second --sysroot when RUSTFLAGS already has one: {contents:?}"
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn write_arg_file_appends_resolved_sysroot_when_absent() {
+        with_env(&[("SYSROOT", Some("/env/sysroot")), ("RUSTFLAGS", None)], || {
+            let invocation = DriverInvocation::new("clippy-driver").flag("-Cdebug-assertions");
+            let file = invocation.write_arg_file().unwrap();
+            let contents = std::fs::read_to_string(file.path()).unwrap();
+            assert!(contents.contains("--sysroot=/env/sysroot"));
+        });
+    }
+}
@@ -0,0 +1,44 @@
+//! Crate-wide error type.
+
+use std::fmt;
+
+/// Anything that can go wrong while collecting, fixing, or reporting
+/// diagnostics.
+#[derive(Debug)]
+pub enum LucidSharkError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    /// The underlying diagnostic source exited with a non-zero status.
+    DriverFailed { status: Option<i32> },
+    Other(String),
+}
+
+pub type Result<T> = std::result::Result<T, LucidSharkError>;
+
+impl fmt::Display for LucidSharkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LucidSharkError::Io(err) => write!(f, "i/o error: {err}"),
+            LucidSharkError::Json(err) => write!(f, "json error: {err}"),
+            LucidSharkError::DriverFailed { status } => match status {
+                Some(code) => write!(f, "diagnostic driver exited with status {code}"),
+                None => write!(f, "diagnostic driver was terminated by a signal"),
+            },
+            LucidSharkError::Other(msg) => write!(f, "{msg}"),
+        }
+    }
+}
+
+impl std::error::Error for LucidSharkError {}
+
+impl From<std::io::Error> for LucidSharkError {
+    fn from(err: std::io::Error) -> Self {
+        LucidSharkError::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for LucidSharkError {
+    fn from(err: serde_json::Error) -> Self {
+        LucidSharkError::Json(err)
+    }
+}
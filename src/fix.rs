@@ -0,0 +1,425 @@
+//! Machine-applicable autofix engine: rewrites source files in place using
+//! the `suggested_edits` carried by `MachineApplicable` diagnostics.
+
+use std::path::{Path, PathBuf};
+
+use crate::diagnostic::{self, Applicability, Diagnostic, Edit};
+use crate::error::Result;
+
+/// Default number of collect/apply passes before giving up and reporting
+/// whatever is left.
+const DEFAULT_MAX_PASSES: usize = 4;
+
+/// Tuning knobs for [`run`].
+pub struct AutofixOptions {
+    /// Maximum number of collect-then-apply passes. Each pass may unlock new
+    /// machine-applicable suggestions that only appear once an earlier fix
+    /// has been applied, so more than one pass is usually needed to reach a
+    /// fixpoint.
+    pub max_passes: usize,
+    /// When set, compute and report fixes without writing any files.
+    pub dry_run: bool,
+}
+
+impl Default for AutofixOptions {
+    fn default() -> Self {
+        AutofixOptions {
+            max_passes: DEFAULT_MAX_PASSES,
+            dry_run: false,
+        }
+    }
+}
+
+/// One fix that was (or would have been) applied, kept for the summary.
+#[derive(Debug, Clone)]
+pub struct AppliedFix {
+    pub file: PathBuf,
+    pub lint: String,
+    pub byte_start: usize,
+    pub byte_end: usize,
+}
+
+/// Outcome of running the autofix engine to completion.
+#[derive(Debug, Default)]
+pub struct AutofixReport {
+    pub passes_run: usize,
+    pub fixes: Vec<AppliedFix>,
+}
+
+/// Repeatedly collects diagnostics from `manifest_dir` and applies every
+/// `MachineApplicable` suggestion, until a pass applies nothing or
+/// `options.max_passes` is reached.
+pub fn run(manifest_dir: &Path, options: &AutofixOptions) -> Result<AutofixReport> {
+    run_with(manifest_dir, || diagnostic::collect(manifest_dir), options)
+}
+
+/// Same loop as [`run`], but takes the diagnostic source as a closure so the
+/// fixpoint/`max_passes` logic can be exercised against canned diagnostics
+/// in tests instead of a real `cargo clippy` invocation.
+fn run_with<F>(manifest_dir: &Path, mut collect: F, options: &AutofixOptions) -> Result<AutofixReport>
+where
+    F: FnMut() -> Result<Vec<Diagnostic>>,
+{
+    let mut report = AutofixReport::default();
+
+    loop {
+        let diagnostics = collect()?;
+        let fixable: Vec<&Diagnostic> = diagnostics
+            .iter()
+            .filter(|d| d.applicability == Some(Applicability::MachineApplicable) && !d.suggested_edits.is_empty())
+            .collect();
+
+        report.passes_run += 1;
+        if fixable.is_empty() {
+            break;
+        }
+
+        let mut applied_this_pass = 0;
+        for (file, mut diags) in diagnostic::by_file(fixable.iter().copied()) {
+            // Apply from the end of the file backwards so that earlier
+            // edits never invalidate the byte offsets of later ones. Diag
+            // files are reported relative to `manifest_dir`, not the
+            // process's own cwd, so that's what they must be joined against.
+            diags.sort_by_key(|d| std::cmp::Reverse(min_edit_start(d)));
+            let fixes = apply_to_file(manifest_dir, &file, &diags, options.dry_run)?;
+            applied_this_pass += fixes.len();
+            report.fixes.extend(fixes);
+        }
+
+        if applied_this_pass == 0 || report.passes_run >= options.max_passes {
+            break;
+        }
+    }
+
+    Ok(report)
+}
+
+fn min_edit_start(diag: &Diagnostic) -> usize {
+    diag.suggested_edits
+        .iter()
+        .map(|edit| edit.byte_start)
+        .min()
+        .unwrap_or(diag.span.byte_start)
+}
+
+fn max_edit_end(diag: &Diagnostic) -> usize {
+    diag.suggested_edits
+        .iter()
+        .map(|edit| edit.byte_end)
+        .max()
+        .unwrap_or(diag.span.byte_end)
+}
+
+/// Applies non-overlapping machine-applicable suggestions to a single file,
+/// resolved against `manifest_dir` (the directory clippy was run in, and so
+/// the directory every diagnostic's file path is relative to).
+///
+/// `diags` must already be sorted by descending edit start. Each
+/// diagnostic's [`Edit`]s are applied together, back-to-front, as a single
+/// unit: a multi-edit suggestion (e.g. `needless_return`, which both
+/// rewrites the `return <expr>` and drops the trailing `;`) only produces
+/// valid code if every one of its edits lands, so if any edit in the group
+/// would overlap a fix already applied, the whole diagnostic is dropped.
+fn apply_to_file(manifest_dir: &Path, file: &Path, diags: &[&Diagnostic], dry_run: bool) -> Result<Vec<AppliedFix>> {
+    let path = manifest_dir.join(file);
+    let mut source = std::fs::read(&path)?;
+    let mut applied = Vec::new();
+    let mut kept_start: Option<usize> = None;
+
+    for diag in diags {
+        if let Some(start) = kept_start {
+            if max_edit_end(diag) > start {
+                continue; // overlaps a fix we've already kept, drop the whole group
+            }
+        }
+
+        let mut edits: Vec<&Edit> = diag.suggested_edits.iter().collect();
+        edits.sort_by_key(|edit| std::cmp::Reverse(edit.byte_start));
+        for edit in edits {
+            source.splice(edit.byte_start..edit.byte_end, edit.replacement.bytes());
+            applied.push(AppliedFix {
+                file: file.to_path_buf(),
+                lint: diag.lint.clone(),
+                byte_start: edit.byte_start,
+                byte_end: edit.byte_end,
+            });
+        }
+        kept_start = Some(min_edit_start(diag));
+    }
+
+    if !dry_run && !applied.is_empty() {
+        std::fs::write(&path, &source)?;
+    }
+
+    Ok(applied)
+}
+
+/// Prints a human-readable summary of which lints were fixed in which files.
+pub fn print_summary(report: &AutofixReport) {
+    if report.fixes.is_empty() {
+        println!("autofix: no machine-applicable suggestions found");
+        return;
+    }
+
+    println!(
+        "autofix: applied {} fix(es) over {} pass(es)",
+        report.fixes.len(),
+        report.passes_run
+    );
+    for fix in &report.fixes {
+        println!("  {} in {}", fix.lint, fix.file.display());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostic::{Level, Span};
+    use std::cell::Cell;
+    use std::io::Write;
+
+    /// A diagnostic whose single suggested edit exactly matches its own
+    /// span.
+    fn diagnostic(file: &Path, byte_start: usize, byte_end: usize, replacement: &str) -> Diagnostic {
+        diagnostic_with_edits(
+            file,
+            byte_start,
+            byte_end,
+            vec![Edit {
+                byte_start,
+                byte_end,
+                replacement: replacement.to_string(),
+            }],
+        )
+    }
+
+    fn diagnostic_with_edits(
+        file: &Path,
+        span_start: usize,
+        span_end: usize,
+        suggested_edits: Vec<Edit>,
+    ) -> Diagnostic {
+        Diagnostic {
+            lint: "clippy::needless_return".to_string(),
+            level: Level::Warning,
+            message: "unnecessary return statement".to_string(),
+            span: Span {
+                file: file.to_path_buf(),
+                byte_start: span_start,
+                byte_end: span_end,
+                line_start: 1,
+                line_end: 1,
+                column_start: span_start + 1,
+                column_end: span_end + 1,
+            },
+            suggested_edits,
+            applicability: Some(Applicability::MachineApplicable),
+        }
+    }
+
+    #[test]
+    fn apply_to_file_drops_spans_that_overlap_an_already_kept_fix() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(b"return a + b;").unwrap();
+        let path = file.path().to_path_buf();
+
+        // "return a + b;" -> suggest replacing the whole statement with
+        // "a + b" (0..13), and also replacing just "a + b" (7..12) with
+        // something else. The second overlaps the first and must be
+        // dropped, since the first comes first in `diags` and is kept.
+        let whole_statement = diagnostic(&path, 0, 13, "a + b");
+        let overlapping_sub_span = diagnostic(&path, 7, 12, "x + y");
+        let diags = vec![&whole_statement, &overlapping_sub_span];
+
+        let applied = apply_to_file(Path::new("/"), &path, &diags, false).unwrap();
+
+        assert_eq!(applied.len(), 1);
+        assert_eq!(applied[0].byte_start, 0);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "a + b");
+    }
+
+    #[test]
+    fn apply_to_file_keeps_non_overlapping_spans_in_descending_order() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(b"aaaa bbbb").unwrap();
+        let path = file.path().to_path_buf();
+
+        let first = diagnostic(&path, 5, 9, "B");
+        let second = diagnostic(&path, 0, 4, "A");
+        let diags = vec![&first, &second];
+
+        let applied = apply_to_file(Path::new("/"), &path, &diags, false).unwrap();
+
+        assert_eq!(applied.len(), 2);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "A B");
+    }
+
+    #[test]
+    fn apply_to_file_splices_the_suggestions_own_span_not_the_diagnostics_primary_span() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(b"use std::collections::HashMap;\nfn main() {}\n").unwrap();
+        let path = file.path().to_path_buf();
+
+        // Mirrors `unused_imports`: the lint is raised on a narrow primary
+        // span inside the `use` item, but the MachineApplicable suggestion
+        // spans the whole item including the trailing newline. Splicing the
+        // replacement into the primary span instead would corrupt the file.
+        let diag = diagnostic_with_edits(
+            &path,
+            4,
+            30,
+            vec![Edit {
+                byte_start: 0,
+                byte_end: 31,
+                replacement: String::new(),
+            }],
+        );
+
+        let applied = apply_to_file(Path::new("/"), &path, &[&diag], false).unwrap();
+
+        assert_eq!(applied.len(), 1);
+        assert_eq!(applied[0].byte_start, 0);
+        assert_eq!(applied[0].byte_end, 31);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "fn main() {}\n");
+    }
+
+    #[test]
+    fn apply_to_file_applies_every_edit_of_a_multi_edit_suggestion_together() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(b"return a.len();").unwrap();
+        let path = file.path().to_path_buf();
+
+        // Mirrors `needless_return`: one edit rewrites `return a.len()` to
+        // `a.len()`, a second, separate edit drops the now-orphaned `;`.
+        // Applying only the first would leave a discarded expression
+        // statement instead of the function's tail return value.
+        let diag = diagnostic_with_edits(
+            &path,
+            0,
+            15,
+            vec![
+                Edit {
+                    byte_start: 0,
+                    byte_end: 14,
+                    replacement: "a.len()".to_string(),
+                },
+                Edit {
+                    byte_start: 14,
+                    byte_end: 15,
+                    replacement: String::new(),
+                },
+            ],
+        );
+
+        let applied = apply_to_file(Path::new("/"), &path, &[&diag], false).unwrap();
+
+        assert_eq!(applied.len(), 2);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "a.len()");
+    }
+
+    #[test]
+    fn apply_to_file_drops_the_whole_group_if_any_of_its_edits_overlaps_an_already_kept_fix() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(b"return a.len();").unwrap();
+        let path = file.path().to_path_buf();
+
+        // A later (lower-offset) two-edit group whose second edit (10..15)
+        // overlaps a fix already kept at 12..15 must be dropped entirely,
+        // not partially applied.
+        let already_kept = diagnostic(&path, 12, 15, "");
+        let overlapping_group = diagnostic_with_edits(
+            &path,
+            0,
+            15,
+            vec![
+                Edit {
+                    byte_start: 0,
+                    byte_end: 7,
+                    replacement: String::new(),
+                },
+                Edit {
+                    byte_start: 10,
+                    byte_end: 15,
+                    replacement: "x".to_string(),
+                },
+            ],
+        );
+        let diags = vec![&already_kept, &overlapping_group];
+
+        let applied = apply_to_file(Path::new("/"), &path, &diags, false).unwrap();
+
+        assert_eq!(applied.len(), 1);
+        assert_eq!(applied[0].byte_start, 12);
+    }
+
+    #[test]
+    fn apply_to_file_resolves_the_file_against_manifest_dir_not_the_process_cwd() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("lib.rs"), b"return a;").unwrap();
+        let relative_file = Path::new("lib.rs");
+
+        // `diag.span.file`/`file` are relative, as clippy reports them when
+        // run with `current_dir(manifest_dir)` - resolving them against the
+        // process's own cwd instead of `manifest_dir` would miss the file.
+        let diag = diagnostic(relative_file, 0, 9, "a");
+
+        let applied = apply_to_file(dir.path(), relative_file, &[&diag], false).unwrap();
+
+        assert_eq!(applied.len(), 1);
+        assert_eq!(std::fs::read_to_string(dir.path().join("lib.rs")).unwrap(), "a");
+    }
+
+    #[test]
+    fn run_with_stops_once_a_pass_applies_nothing() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(b"return a;").unwrap();
+        let path = file.path().to_path_buf();
+
+        // Pass 1 fixes `return a;` -> `a`. Pass 2 finds nothing left to fix
+        // and the loop should stop there, without reaching `max_passes`.
+        let pass = Cell::new(0);
+        let options = AutofixOptions {
+            max_passes: 10,
+            dry_run: false,
+        };
+
+        let report = run_with(
+            Path::new("/"),
+            || {
+                let current = pass.get();
+                pass.set(current + 1);
+                Ok(if current == 0 {
+                    vec![diagnostic(&path, 0, 9, "a")]
+                } else {
+                    Vec::new()
+                })
+            },
+            &options,
+        )
+        .unwrap();
+
+        assert_eq!(report.fixes.len(), 1);
+        assert_eq!(report.passes_run, 2);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "a");
+    }
+
+    #[test]
+    fn run_with_gives_up_at_max_passes_if_fixes_keep_appearing() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(b"xxxx").unwrap();
+        let path = file.path().to_path_buf();
+
+        // Every pass reports a fixable diagnostic (as if new ones kept
+        // unlocking), so the loop must be bounded by `max_passes` rather
+        // than running forever.
+        let options = AutofixOptions {
+            max_passes: 3,
+            dry_run: true,
+        };
+
+        let report = run_with(Path::new("/"), || Ok(vec![diagnostic(&path, 0, 1, "y")]), &options).unwrap();
+
+        assert_eq!(report.passes_run, 3);
+        assert_eq!(report.fixes.len(), 3);
+    }
+}
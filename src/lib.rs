@@ -0,0 +1,13 @@
+//! LucidShark: collects clippy diagnostics and applies, exports, or explains
+//! them.
+
+pub mod cli;
+pub mod config;
+pub mod diagnostic;
+pub mod driver;
+pub mod error;
+pub mod fix;
+pub mod lints;
+pub mod sarif;
+
+pub use error::{LucidSharkError, Result};
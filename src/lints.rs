@@ -0,0 +1,136 @@
+//! Catalog of every lint LucidShark can report: name, category, default
+//! level, and a short description. Backs the `collect-metadata` subcommand
+//! and lets `lucidshark.toml` validate lint names against something more
+//! complete than a scattered, ad hoc list.
+
+use serde::Serialize;
+
+/// Broad grouping a lint belongs to, mirroring clippy's own lint groups.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Category {
+    Correctness,
+    Style,
+    Complexity,
+    Perf,
+}
+
+/// Everything known about a single lint, independent of whether it has
+/// actually fired on any given run.
+#[derive(Debug, Clone, Serialize)]
+pub struct LintMetadata {
+    pub name: &'static str,
+    pub category: Category,
+    pub default_level: &'static str,
+    pub description: &'static str,
+}
+
+const REGISTRY: &[LintMetadata] = &[
+    LintMetadata {
+        name: "clippy::redundant_clone",
+        category: Category::Perf,
+        default_level: "warning",
+        description: "Checks for a clone() that duplicates a value which is never used again.",
+    },
+    LintMetadata {
+        name: "clippy::manual_map",
+        category: Category::Style,
+        default_level: "warning",
+        description: "Checks for a match on an Option that could be an Option::map call instead.",
+    },
+    LintMetadata {
+        name: "clippy::needless_return",
+        category: Category::Style,
+        default_level: "warning",
+        description: "Checks for a return statement at the end of a block that could be an expression.",
+    },
+    LintMetadata {
+        name: "clippy::needless_range_loop",
+        category: Category::Complexity,
+        default_level: "warning",
+        description: "Checks for range loops that could be replaced with an iterator.",
+    },
+    LintMetadata {
+        name: "unused_imports",
+        category: Category::Correctness,
+        default_level: "warning",
+        description: "Checks for imports that are never referenced.",
+    },
+    LintMetadata {
+        name: "clippy::new_without_default",
+        category: Category::Style,
+        default_level: "warning",
+        description: "Checks for a public new() with no arguments whose type could implement Default.",
+    },
+    LintMetadata {
+        name: "clippy::unnecessary_get_then_check",
+        category: Category::Style,
+        default_level: "warning",
+        description: "Checks for `.get(key).is_some()` that could use `.contains_key(key)` instead.",
+    },
+];
+
+/// Returns every lint LucidShark knows about.
+pub fn all() -> &'static [LintMetadata] {
+    REGISTRY
+}
+
+/// Looks up a single lint by its fully qualified name.
+pub fn find(name: &str) -> Option<&'static LintMetadata> {
+    REGISTRY.iter().find(|lint| lint.name == name)
+}
+
+/// Renders the catalog as pretty-printed JSON.
+pub fn to_json() -> Result<String, serde_json::Error> {
+    serde_json::to_string_pretty(&REGISTRY)
+}
+
+/// Renders the catalog as a Markdown table.
+pub fn to_markdown() -> String {
+    let mut out = String::from("| Lint | Category | Default level | Description |\n|---|---|---|---|\n");
+    for lint in REGISTRY {
+        out.push_str(&format!(
+            "| `{}` | {:?} | {} | {} |\n",
+            lint.name, lint.category, lint.default_level, lint.description
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn catalog_covers_every_lint_the_parser_yields_from_the_sample_fixture() {
+        // Drive the actual parser over the sample `rust-cli` fixture rather
+        // than hand-maintaining a parallel list of lints it's expected to
+        // trigger - this way the test fails the moment the catalog and the
+        // parser's real output drift apart.
+        let fixture =
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/integration/projects/rust-cli");
+        let diagnostics = crate::diagnostic::collect(&fixture)
+            .expect("collecting diagnostics from the sample fixture");
+
+        assert!(
+            !diagnostics.is_empty(),
+            "expected the sample fixture to trigger at least one lint"
+        );
+        for diagnostic in &diagnostics {
+            assert!(
+                find(&diagnostic.lint).is_some(),
+                "parser yielded `{}`, which is missing from the registry",
+                diagnostic.lint
+            );
+        }
+    }
+
+    #[test]
+    fn catalog_has_no_duplicate_names() {
+        let mut names: Vec<&str> = REGISTRY.iter().map(|lint| lint.name).collect();
+        names.sort_unstable();
+        names.dedup();
+        assert_eq!(names.len(), REGISTRY.len(), "registry contains duplicate lint names");
+    }
+}
@@ -0,0 +1,193 @@
+//! SARIF 2.1.0 output, so results can be consumed by code-scanning
+//! dashboards and editor integrations alongside the human-readable report.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::diagnostic::{Diagnostic, Level};
+
+const SARIF_VERSION: &str = "2.1.0";
+const SARIF_SCHEMA: &str =
+    "https://raw.githubusercontent.com/oasis-tcs/sarif-spec/master/Schemata/sarif-schema-2.1.0.json";
+const TOOL_NAME: &str = "lucidshark";
+
+#[derive(Serialize)]
+pub struct SarifLog {
+    pub version: String,
+    #[serde(rename = "$schema")]
+    pub schema: String,
+    pub runs: Vec<Run>,
+}
+
+#[derive(Serialize)]
+pub struct Run {
+    pub tool: Tool,
+    pub results: Vec<SarifResult>,
+}
+
+#[derive(Serialize)]
+pub struct Tool {
+    pub driver: Driver,
+}
+
+#[derive(Serialize)]
+pub struct Driver {
+    pub name: String,
+    pub rules: Vec<Rule>,
+}
+
+#[derive(Serialize)]
+pub struct Rule {
+    pub id: String,
+    #[serde(rename = "helpUri")]
+    pub help_uri: String,
+    #[serde(rename = "defaultConfiguration")]
+    pub default_configuration: RuleConfiguration,
+}
+
+#[derive(Serialize)]
+pub struct RuleConfiguration {
+    pub level: String,
+}
+
+#[derive(Serialize)]
+pub struct SarifResult {
+    #[serde(rename = "ruleId")]
+    pub rule_id: String,
+    pub level: String,
+    pub message: Message,
+    pub locations: Vec<Location>,
+}
+
+#[derive(Serialize)]
+pub struct Message {
+    pub text: String,
+}
+
+#[derive(Serialize)]
+pub struct Location {
+    #[serde(rename = "physicalLocation")]
+    pub physical_location: PhysicalLocation,
+}
+
+#[derive(Serialize)]
+pub struct PhysicalLocation {
+    #[serde(rename = "artifactLocation")]
+    pub artifact_location: ArtifactLocation,
+    pub region: Region,
+}
+
+#[derive(Serialize)]
+pub struct ArtifactLocation {
+    pub uri: String,
+}
+
+#[derive(Serialize)]
+pub struct Region {
+    #[serde(rename = "startLine")]
+    pub start_line: usize,
+    #[serde(rename = "startColumn")]
+    pub start_column: usize,
+    #[serde(rename = "endLine")]
+    pub end_line: usize,
+    #[serde(rename = "endColumn")]
+    pub end_column: usize,
+}
+
+/// Builds a SARIF 2.1.0 log from a flat diagnostic list, deriving the
+/// `tool.driver.rules` catalog from the distinct lints that actually fired.
+pub fn build(diagnostics: &[Diagnostic]) -> SarifLog {
+    let mut rules: BTreeMap<String, Rule> = BTreeMap::new();
+    let mut results = Vec::with_capacity(diagnostics.len());
+
+    for diagnostic in diagnostics {
+        rules.entry(diagnostic.lint.clone()).or_insert_with(|| Rule {
+            id: diagnostic.lint.clone(),
+            help_uri: help_uri(&diagnostic.lint),
+            default_configuration: RuleConfiguration {
+                level: sarif_level(diagnostic.level),
+            },
+        });
+
+        results.push(SarifResult {
+            rule_id: diagnostic.lint.clone(),
+            level: sarif_level(diagnostic.level),
+            message: Message {
+                text: diagnostic.message.clone(),
+            },
+            locations: vec![Location {
+                physical_location: PhysicalLocation {
+                    artifact_location: ArtifactLocation {
+                        uri: file_uri(&diagnostic.span.file),
+                    },
+                    region: Region {
+                        start_line: diagnostic.span.line_start,
+                        start_column: diagnostic.span.column_start,
+                        end_line: diagnostic.span.line_end,
+                        end_column: diagnostic.span.column_end,
+                    },
+                },
+            }],
+        });
+    }
+
+    SarifLog {
+        version: SARIF_VERSION.to_string(),
+        schema: SARIF_SCHEMA.to_string(),
+        runs: vec![Run {
+            tool: Tool {
+                driver: Driver {
+                    name: TOOL_NAME.to_string(),
+                    rules: rules.into_values().collect(),
+                },
+            },
+            results,
+        }],
+    }
+}
+
+fn sarif_level(level: Level) -> String {
+    match level {
+        Level::Error => "error",
+        _ => "warning",
+    }
+    .to_string()
+}
+
+fn help_uri(lint: &str) -> String {
+    match lint.strip_prefix("clippy::") {
+        Some(name) => format!("https://rust-lang.github.io/rust-clippy/master/index.html#{name}"),
+        None => format!("https://doc.rust-lang.org/rustc/lints/listing/index.html#{lint}"),
+    }
+}
+
+/// Renders `path` as a SARIF `artifactLocation.uri`. clippy reports paths
+/// relative to the crate root, and a bare `file://<relative path>` parses
+/// the first path component as the URI authority (e.g. `file://src/lib.rs`
+/// treats `src` as the host); emit relative paths without a scheme instead,
+/// and only use `file://` for paths that are already absolute.
+fn file_uri(path: &Path) -> String {
+    if path.is_absolute() {
+        format!("file://{}", path.display())
+    } else {
+        path.display().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn relative_paths_have_no_scheme() {
+        assert_eq!(file_uri(&PathBuf::from("src/lib.rs")), "src/lib.rs");
+    }
+
+    #[test]
+    fn absolute_paths_get_a_file_scheme() {
+        assert_eq!(file_uri(&PathBuf::from("/crate/src/lib.rs")), "file:///crate/src/lib.rs");
+    }
+}